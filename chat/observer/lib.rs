@@ -1,6 +1,6 @@
 mod lock;
 mod read_guard;
-mod shared;
+pub mod shared;
 mod state;
-mod subscriber;
-mod unique;
+pub mod subscriber;
+pub mod unique;
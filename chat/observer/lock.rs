@@ -1,12 +1,12 @@
 use std::{
     ops::{Deref, DerefMut},
-    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError},
 };
 
 use crate::{
     shared::{Shared, SharedReadGuard, SharedReadLock},
     state::ObservableState,
-    subscriber::Subscriber,
+    subscriber::{Subscriber, SubscriberId},
 };
 
 pub trait Lock {
@@ -26,6 +26,8 @@ pub trait Lock {
     fn new_shared<T>(value: T) -> Self::Shared<T>;
     fn shared_read_count<T>(shared: &Self::Shared<T>) -> usize;
     fn shared_into_inner<T>(shared: Self::Shared<T>) -> Arc<Self::RwLock<T>>;
+
+    fn remove_subscriber_waker<S>(state: &Self::SubscriberState<S>, id: SubscriberId);
 }
 
 pub enum SyncLock {}
@@ -48,7 +50,12 @@ impl Lock for SyncLock {
         Self::RwLock::new(value)
     }
     fn read_noblock<T>(lock: &Self::RwLock<T>) -> Self::RwLockReadGuard<'_, T> {
-        lock.try_read().unwrap()
+        // Used from `Drop`, so a poisoned lock must not cause a panic.
+        match lock.try_read() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(err)) => err.into_inner(),
+            Err(TryLockError::WouldBlock) => panic!("read_noblock called on a locked RwLock"),
+        }
     }
 
     fn new_shared<T>(value: T) -> Self::Shared<T> {
@@ -60,6 +67,17 @@ impl Lock for SyncLock {
     fn shared_into_inner<T>(shared: Self::Shared<T>) -> Arc<Self::RwLock<T>> {
         Self::Shared::into_inner(shared)
     }
+
+    fn remove_subscriber_waker<S>(state: &Self::SubscriberState<S>, id: SubscriberId) {
+        // Never panic or block here, this runs when a subscriber is dropped,
+        // possibly during unwinding.
+        let state = match state.try_lock() {
+            Ok(state) => state,
+            Err(TryLockError::Poisoned(err)) => err.into_inner(),
+            Err(TryLockError::WouldBlock) => return,
+        };
+        state.remove_waker(id);
+    }
 }
 
 #[must_use]
@@ -1,10 +1,11 @@
+use std::ops::Deref;
+
 use crate::{
     lock::{Lock, SyncLock},
     state::ObservableState,
 };
-use derive_tools::Deref;
 
-#[derive(Debug, Deref)]
+#[derive(Debug)]
 pub struct ObservableReadGuard<'a, T: 'a, L: Lock = SyncLock> {
     inner: L::SharedReadGuard<'a, ObservableState<T>>,
 }
@@ -14,3 +15,11 @@ impl<'a, T: 'a, L: Lock> ObservableReadGuard<'a, T, L> {
         Self { inner }
     }
 }
+
+impl<T> Deref for ObservableReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.inner.get()
+    }
+}
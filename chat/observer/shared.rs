@@ -8,6 +8,8 @@ use std::{
 
 use derive_tools::*;
 
+#[cfg(feature = "metrics")]
+pub use crate::state::ObservableStats;
 use crate::{
     lock::{Lock, SyncLock},
     read_guard::ObservableReadGuard,
@@ -265,6 +267,18 @@ impl<T> SharedObservable<T> {
         self.state.read().unwrap().get().clone()
     }
 
+    /// Get the update counters of this observable.
+    ///
+    /// These can be used to see how many updates were coalesced, that is
+    /// skipped by subscribers that didn't keep up with the rate of updates.
+    ///
+    /// Only available with the `metrics` feature.
+    #[cfg(feature = "metrics")]
+    #[must_use]
+    pub fn stats(&self) -> ObservableStats {
+        self.state.read().unwrap().stats()
+    }
+
     /// Lock the inner with shared read access, blocking the current thread
     /// until the lock can be acquired.
     ///
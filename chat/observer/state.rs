@@ -1,10 +1,13 @@
 use std::{
+    collections::HashMap,
     hash::{Hash, Hasher},
     mem,
-    sync::RwLock,
+    sync::{RwLock, TryLockError},
     task::{Context, Poll, Waker},
 };
 
+use crate::subscriber::SubscriberId;
+
 #[derive(Debug)]
 pub struct ObservableState<T> {
     /// The wrapped value.
@@ -23,21 +26,56 @@ struct ObservableStateMetadata {
     /// updates will happen.
     version: u64,
 
-    /// List of wakers.
+    /// Wakers of subscribers waiting for an update, keyed by subscriber.
+    ///
+    /// Every subscriber has its own entry, so polling a subscriber again
+    /// replaces its previous waker instead of piling up clones of it, and
+    /// subscribers polled from the same task (e.g. in `select!`) each get
+    /// woken.
     ///
     /// This is part of `ObservableState` and uses extra locking so that it is
     /// guaranteed that it's only updated by subscribers while the value is
     /// locked for reading. This way, it is guaranteed that between a subscriber
     /// reading the value and adding a waker because the value hasn't changed
     /// yet, no updates to the value could have happened.
-    wakers: Vec<Waker>,
+    wakers: HashMap<SubscriberId, Waker>,
+
+    /// Counters for tuning, see [`ObservableStats`].
+    #[cfg(feature = "metrics")]
+    stats: ObservableStats,
+}
+
+/// Counters describing how updates of an observable were delivered.
+///
+/// Only available with the `metrics` feature.
+#[cfg(feature = "metrics")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ObservableStats {
+    /// The number of times the value was updated and subscribers notified.
+    pub sets: u64,
+
+    /// The number of updates that were received by subscribers.
+    pub notifications: u64,
+
+    /// The number of updates that subscribers never saw, because a newer
+    /// update was already available when they polled.
+    ///
+    /// This is the sum over all subscribers, including ones that were dropped
+    /// since. Skipped updates are only counted when a subscriber polls, so
+    /// updates a subscriber hasn't polled for yet are not included. See
+    /// [`Subscriber::coalesced`][crate::subscriber::Subscriber::coalesced] for
+    /// the count of a single subscriber.
+    pub coalesced: u64,
 }
 
 impl Default for ObservableStateMetadata {
     fn default() -> Self {
         Self {
             version: 1,
-            wakers: Vec::new(),
+            wakers: HashMap::new(),
+            #[cfg(feature = "metrics")]
+            stats: ObservableStats::default(),
         }
     }
 }
@@ -60,8 +98,15 @@ impl<T> ObservableState<T> {
         self.metadata.read().unwrap().version
     }
 
+    /// Get the update counters.
+    #[cfg(feature = "metrics")]
+    pub(crate) fn stats(&self) -> ObservableStats {
+        self.metadata.read().unwrap().stats
+    }
+
     pub(crate) fn poll_update(
         &self,
+        id: SubscriberId,
         observed_version: &mut u64,
         cx: &Context<'_>,
     ) -> Poll<Option<()>> {
@@ -70,14 +115,34 @@ impl<T> ObservableState<T> {
         if metadata.version == 0 {
             Poll::Ready(None)
         } else if *observed_version < metadata.version {
+            #[cfg(feature = "metrics")]
+            {
+                let skipped = coalesced_updates(*observed_version, metadata.version);
+                metadata.stats.notifications += 1;
+                metadata.stats.coalesced += skipped;
+            }
             *observed_version = metadata.version;
             Poll::Ready(Some(()))
         } else {
-            metadata.wakers.push(cx.waker().clone());
+            metadata.wakers.insert(id, cx.waker().clone());
             Poll::Pending
         }
     }
 
+    /// Remove the waker registered by the given subscriber, if any.
+    ///
+    /// This is called when a subscriber is dropped, so it must not panic or
+    /// block. If the metadata is locked, cleanup is skipped; a leftover waker
+    /// is harmless and gets drained on the next update.
+    pub(crate) fn remove_waker(&self, id: SubscriberId) {
+        let mut metadata = match self.metadata.try_write() {
+            Ok(metadata) => metadata,
+            Err(TryLockError::Poisoned(err)) => err.into_inner(),
+            Err(TryLockError::WouldBlock) => return,
+        };
+        metadata.wakers.remove(&id);
+    }
+
     pub(crate) fn set(&mut self, value: T) -> T {
         let result = mem::replace(&mut self.value, value);
         self.incr_version_and_wake();
@@ -122,13 +187,28 @@ impl<T> ObservableState<T> {
         let mut metadata = self.metadata.write().unwrap();
         metadata.version = 0;
         // Clear the backing buffer for the wakers, no new ones will be added.
-        wake(mem::take(&mut metadata.wakers));
+        wake(mem::take(&mut metadata.wakers).into_values());
     }
 
     fn incr_version_and_wake(&mut self) {
         let metadata = self.metadata.get_mut().unwrap();
         metadata.version += 1;
-        wake(metadata.wakers.drain(..));
+        #[cfg(feature = "metrics")]
+        {
+            metadata.stats.sets += 1;
+        }
+        wake(metadata.wakers.drain().map(|(_, waker)| waker));
+    }
+}
+
+/// The number of updates skipped by a subscriber that observed
+/// `observed_version` and is now receiving `version`.
+#[cfg(feature = "metrics")]
+pub(crate) fn coalesced_updates(observed_version: u64, version: u64) -> u64 {
+    // A version of 0 means the subscriber was reset, not that it fell behind.
+    match observed_version {
+        0 => 0,
+        observed => version - observed - 1,
     }
 }
 
@@ -150,3 +230,63 @@ where
         waker.wake();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::Arc,
+        task::{Context, Wake, Waker},
+    };
+
+    use super::ObservableState;
+    use crate::{shared::Shared, subscriber::Subscriber};
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    fn new_waker() -> Waker {
+        Waker::from(Arc::new(NoopWaker))
+    }
+
+    fn subscribe(shared: &Shared<ObservableState<i32>>) -> Subscriber<i32> {
+        Subscriber::new(Shared::get_read_lock(shared), shared.version())
+    }
+
+    fn wakers<T>(state: &ObservableState<T>) -> Vec<Waker> {
+        state.metadata.read().unwrap().wakers.values().cloned().collect()
+    }
+
+    #[test]
+    fn repoll_replaces_waker() {
+        let shared = Shared::new(ObservableState::new(0));
+        let mut sub = subscribe(&shared);
+
+        let (waker1, waker2) = (new_waker(), new_waker());
+        assert!(sub.poll_update(&Context::from_waker(&waker1)).is_pending());
+        assert!(sub.poll_update(&Context::from_waker(&waker2)).is_pending());
+
+        let wakers = wakers(&shared);
+        assert_eq!(wakers.len(), 1);
+        assert!(wakers[0].will_wake(&waker2));
+    }
+
+    #[test]
+    fn drop_removes_waker() {
+        let shared = Shared::new(ObservableState::new(0));
+        let mut sub1 = subscribe(&shared);
+        let mut sub2 = subscribe(&shared);
+
+        let waker = new_waker();
+        assert!(sub1.poll_update(&Context::from_waker(&waker)).is_pending());
+        assert!(sub2.poll_update(&Context::from_waker(&waker)).is_pending());
+        assert_eq!(wakers(&shared).len(), 2);
+
+        drop(sub1);
+        assert_eq!(wakers(&shared).len(), 1);
+        drop(sub2);
+        assert_eq!(wakers(&shared).len(), 0);
+    }
+}
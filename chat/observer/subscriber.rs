@@ -1,22 +1,161 @@
-use std::sync::{Arc, RwLock};
+use std::{
+    error::Error,
+    fmt,
+    future::poll_fn,
+    sync::atomic::{AtomicU64, Ordering},
+    task::{Context, Poll},
+};
 
 use crate::{
     lock::{Lock, SyncLock},
+    read_guard::ObservableReadGuard,
     shared::SharedReadLock,
     state::ObservableState,
 };
+#[cfg(feature = "metrics")]
+use crate::state::coalesced_updates;
+
+/// Identifies a subscriber in the waker set of its observable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SubscriberId(u64);
+
+impl SubscriberId {
+    fn next() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
 
 #[must_use]
 pub struct Subscriber<T, L: Lock = SyncLock> {
     state: L::SubscriberState<T>,
+    id: SubscriberId,
     observed_version: u64,
+    /// The number of updates this subscriber skipped, see
+    /// [`coalesced`][Self::coalesced].
+    #[cfg(feature = "metrics")]
+    coalesced: u64,
 }
 
 impl<T> Subscriber<T> {
     pub(crate) fn new(state: SharedReadLock<ObservableState<T>>, version: u64) -> Self {
         Self {
             state,
+            id: SubscriberId::next(),
             observed_version: version,
+            #[cfg(feature = "metrics")]
+            coalesced: 0,
+        }
+    }
+
+    /// Get the number of updates this subscriber never saw, because a newer
+    /// update was already available when it polled.
+    ///
+    /// Skipped updates are only counted when the subscriber polls, so a
+    /// subscriber that never polls reports `0`.
+    ///
+    /// Only available with the `metrics` feature.
+    #[cfg(feature = "metrics")]
+    #[must_use]
+    pub fn coalesced(&self) -> u64 {
+        self.coalesced
+    }
+
+    /// Wait for an update and get a clone of the updated value.
+    ///
+    /// Awaiting returns `Some(_)` after an update happened, or `None` after the
+    /// `Observable` (and all clones for `SharedObservable`) is dropped.
+    pub async fn next(&mut self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.next_ref().await.map(|guard| T::clone(&guard))
+    }
+
+    /// Wait for an update and get a clone of the updated value, with an
+    /// explicit error once no further updates can happen.
+    ///
+    /// This is like [`next`][Self::next], but returns
+    /// `Err(RecvError::Closed)` after the `Observable` (and all clones for
+    /// `SharedObservable`) is dropped, mirroring the `recv` method of
+    /// channels.
+    pub async fn recv(&mut self) -> Result<T, RecvError>
+    where
+        T: Clone,
+    {
+        self.next().await.ok_or(RecvError::Closed)
+    }
+
+    /// Wait for an update and get a read lock for the updated value.
+    ///
+    /// Awaiting returns `Some(_)` after an update happened, or `None` after the
+    /// `Observable` (and all clones for `SharedObservable`) is dropped.
+    ///
+    /// You can use this method to get updates of an `Observable` where the
+    /// inner type does not implement `Clone`. However, the `Observable`
+    /// will be locked (not updateable) while any read guards are alive.
+    pub async fn next_ref(&mut self) -> Option<ObservableReadGuard<'_, T>> {
+        poll_fn(|cx| self.poll_update(cx)).await?;
+        Some(self.read())
+    }
+
+    /// Get a clone of the inner value without waiting for an update.
+    #[must_use]
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        T::clone(&self.read())
+    }
+
+    /// Lock the inner value for reading without waiting for an update.
+    ///
+    /// Note that as long as the returned [`ObservableReadGuard`] is kept alive,
+    /// the associated `Observable` is locked and can not be updated.
+    pub fn read(&self) -> ObservableReadGuard<'_, T> {
+        ObservableReadGuard::new(self.state.lock())
+    }
+
+    /// Reset the observed version of the inner value.
+    ///
+    /// After calling this, it is guaranteed that the next call to
+    /// `.next().await` or `.next_ref().await` will resolve immediately.
+    pub fn reset(&mut self) {
+        self.observed_version = 0;
+    }
+
+    pub(crate) fn poll_update(&mut self, cx: &Context<'_>) -> Poll<Option<()>> {
+        let state = self.state.lock();
+        #[cfg(feature = "metrics")]
+        let observed_version = self.observed_version;
+        let poll = state.poll_update(self.id, &mut self.observed_version, cx);
+        #[cfg(feature = "metrics")]
+        if let Poll::Ready(Some(())) = poll {
+            self.coalesced += coalesced_updates(observed_version, self.observed_version);
+        }
+        poll
+    }
+}
+
+/// Error returned by [`Subscriber::recv`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecvError {
+    /// The observable was dropped, no further updates will happen.
+    Closed,
+}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Closed => f.write_str("observable closed"),
         }
     }
 }
+
+impl Error for RecvError {}
+
+impl<T, L: Lock> Drop for Subscriber<T, L> {
+    fn drop(&mut self) {
+        L::remove_subscriber_waker(&self.state, self.id);
+    }
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "metrics")]
+
+use observer::shared::SharedObservable;
+
+#[tokio::test]
+async fn slow_subscriber_coalesces() {
+    let ob = SharedObservable::new(0);
+    let mut slow = ob.subscribe();
+    let mut fast = ob.subscribe();
+
+    for i in 1..=5 {
+        ob.set(i);
+        assert_eq!(fast.next().await, Some(i));
+    }
+    assert_eq!(slow.next().await, Some(5));
+
+    let stats = ob.stats();
+    assert_eq!(stats.sets, 5);
+    assert_eq!(stats.notifications, 6);
+    assert_eq!(stats.coalesced, 4);
+    assert_eq!(slow.coalesced(), 4);
+    assert_eq!(fast.coalesced(), 0);
+}
+
+#[tokio::test]
+async fn coalescing_counted_on_poll() {
+    let ob = SharedObservable::new(0);
+    let mut rx = ob.subscribe();
+
+    ob.set(1);
+    ob.set(2);
+    assert_eq!(ob.stats().coalesced, 0);
+    assert_eq!(rx.coalesced(), 0);
+
+    assert_eq!(rx.next().await, Some(2));
+    assert_eq!(ob.stats().coalesced, 1);
+    assert_eq!(rx.coalesced(), 1);
+}
+
+#[tokio::test]
+async fn reset_is_not_coalescing() {
+    let ob = SharedObservable::new(0);
+    ob.set(1);
+    ob.set(2);
+
+    let mut rx = ob.subscribe_reset();
+    assert_eq!(rx.next().await, Some(2));
+
+    let stats = ob.stats();
+    assert_eq!(stats.notifications, 1);
+    assert_eq!(stats.coalesced, 0);
+    assert_eq!(rx.coalesced(), 0);
+}
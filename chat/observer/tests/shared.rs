@@ -1,5 +1,6 @@
 use observer::shared::SharedObservable;
 
+#[tokio::test]
 async fn lag() {
     let ob = SharedObservable::new("hello, world!".to_owned());
     let mut rx1 = ob.subscribe();
@@ -13,6 +14,7 @@ async fn lag() {
     assert_eq!(rx2.next().await, Some("B".to_owned()));
 }
 
+#[tokio::test]
 async fn separate_tasks() {
     let ob = SharedObservable::new(Box::new([0; 256]));
     let mut subscriber = ob.subscribe();
@@ -33,9 +35,10 @@ async fn separate_tasks() {
         drop(ob);
     };
 
-    join(recv_fut, set_fut).await;
+    tokio::join!(recv_fut, set_fut);
 }
 
+#[tokio::test]
 async fn lag_no_clone() {
     // no Clone impl
     struct Foo(String);
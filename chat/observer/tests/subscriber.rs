@@ -0,0 +1,58 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use observer::{shared::SharedObservable, subscriber::RecvError};
+
+// This checks that no wakeup is lost when many subscribers of one observable
+// are polled from the same task. It would also pass with a plain list of
+// wakers; replacing a subscriber's waker on re-poll and removing it on drop
+// are covered by the unit tests in `state.rs`.
+#[tokio::test]
+async fn select_wakes_all_subscribers() {
+    let ob = SharedObservable::new(0);
+    let mut rx1 = ob.subscribe();
+    let mut rx2 = ob.subscribe();
+    let mut rx3 = ob.subscribe();
+
+    let mut seen = [None; 3];
+    let select_fut = async {
+        for _ in 0..3 {
+            tokio::select! {
+                Some(value) = rx1.next(), if seen[0].is_none() => seen[0] = Some(value),
+                Some(value) = rx2.next(), if seen[1].is_none() => seen[1] = Some(value),
+                Some(value) = rx3.next(), if seen[2].is_none() => seen[2] = Some(value),
+            }
+        }
+    };
+    let set_fut = async {
+        tokio::task::yield_now().await;
+        ob.set(1);
+    };
+
+    tokio::join!(select_fut, set_fut);
+    assert_eq!(seen, [Some(1); 3]);
+}
+
+#[tokio::test]
+async fn recv_closed() {
+    let ob = SharedObservable::new(0);
+    let mut rx = ob.subscribe();
+
+    ob.set(1);
+    assert_eq!(rx.recv().await, Ok(1));
+
+    drop(ob);
+    assert_eq!(rx.recv().await, Err(RecvError::Closed));
+}
+
+#[test]
+fn drop_after_poison() {
+    let ob = SharedObservable::new(0);
+    let rx = ob.subscribe();
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        ob.update(|_| panic!("poison the lock"));
+    }));
+    assert!(result.is_err());
+
+    drop(rx);
+}
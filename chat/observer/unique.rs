@@ -52,6 +52,29 @@ impl<T> Observable<T> {
         this.state.get()
     }
 
+    /// Get a reference to the inner value, using method-call syntax.
+    ///
+    /// This is the same as [`Observable::get`], but doesn't need the
+    /// `Observable::` prefix:
+    ///
+    /// ```
+    /// use observer::unique::Observable;
+    ///
+    /// let mut ob = Observable::new(1);
+    /// assert_eq!(*ob.get_value(), 1);
+    ///
+    /// Observable::set(&mut ob, 2);
+    /// assert_eq!(*ob.get_value(), 2);
+    /// ```
+    ///
+    /// Only reading is available as a method. Functions that modify the inner
+    /// value (`set`, `update` and so on) stay associated functions, so that
+    /// every write goes through a function that notifies subscribers.
+    #[must_use]
+    pub fn get_value(&self) -> &T {
+        self.state.get()
+    }
+
     /// Set the inner value to the given `value`, notify subscribers and return
     /// the previous value.
     pub fn set(this: &mut Self, value: T) -> T {